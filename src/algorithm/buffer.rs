@@ -0,0 +1,45 @@
+#[cfg(feature = "std")]
+use image::GrayImage;
+
+#[cfg(not(feature = "std"))]
+use algorithm::raw::RawGray;
+
+/// A single-channel 8-bit pixel source, abstracting over `image::GrayImage`
+/// (`std`) and `RawGray` (`no_std`) so module-sampling logic (`sample`) and
+/// finder-pattern scanning logic (`finder`) only have to be written once and
+/// run identically in both build modes.
+pub trait GrayBuffer {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    fn get(&self, x: u32, y: u32) -> u8;
+}
+
+#[cfg(feature = "std")]
+impl GrayBuffer for GrayImage {
+    fn width(&self) -> u32 {
+        GrayImage::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        GrayImage::height(self)
+    }
+
+    fn get(&self, x: u32, y: u32) -> u8 {
+        self.get_pixel(x, y)[0]
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl GrayBuffer for RawGray {
+    fn width(&self) -> u32 {
+        RawGray::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        RawGray::height(self)
+    }
+
+    fn get(&self, x: u32, y: u32) -> u8 {
+        RawGray::get(self, x, y)
+    }
+}
@@ -0,0 +1,601 @@
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use qr::blocks::deinterleave;
+use qr::correct::correct_with_erasures;
+use qr::data::{DecodedSegment, Mode};
+use qr::eci::{self, Charset};
+use qr::{block_info, micro_block_info, QRData, QRError, QRLocation, ScanResult};
+
+/// Turns an extracted, unmasked module grid into the string it encodes.
+pub trait Decode {
+    fn decode(&self, extraction: Vec<Result<QRData, QRError>>) -> Vec<Result<String, QRError>>;
+
+    /// As `decode`, but returns the raw bytes of each symbol's segments
+    /// rather than a lossily-converted `String`, so byte-mode payloads that
+    /// aren't valid UTF-8 (binary formats, keys, protocol blobs) survive
+    /// intact.
+    fn decode_to_bytes(
+        &self,
+        extraction: Vec<Result<QRData, QRError>>,
+    ) -> Vec<Result<Vec<u8>, QRError>>;
+
+    /// As `decode`, but pairs each symbol's decoded text with the rest of
+    /// its metadata - `QRLocation`'s finder geometry plus the version, EC
+    /// level, mask and Reed-Solomon error count recovered while decoding -
+    /// in a `ScanResult`, so callers can do quality gating, overlay drawing
+    /// or multi-code disambiguation without re-running the locate stage.
+    fn decode_detailed(
+        &self,
+        extraction: Vec<(QRLocation, Result<QRData, QRError>)>,
+    ) -> Vec<Result<ScanResult, QRError>>;
+}
+
+/// Decodes a standard QR symbol's module grid: reads codewords off the grid
+/// in the spec's zigzag order, de-interleaves and Reed-Solomon corrects each
+/// block, then walks the resulting bitstream's mode indicators to build up
+/// the decoded segments.
+pub struct QRDecoder {}
+
+impl QRDecoder {
+    pub fn new() -> QRDecoder {
+        QRDecoder {}
+    }
+
+    /// As `decode`'s per-symbol step, but also returns the number of
+    /// codeword errors Reed-Solomon corrected while recovering the message,
+    /// for `decode_detailed`'s `ScanResult::errors_corrected`.
+    fn segments_for(&self, data: QRData) -> Result<(Vec<DecodedSegment>, u32), QRError> {
+        if data.micro {
+            return self.micro_segments_for(data);
+        }
+
+        let (codewords, erasures) = read_codewords(&data);
+        let (blocks, block_erasures) =
+            deinterleave(&codewords, &erasures, data.version, data.level)?;
+        let info = block_info(data.version, data.level)
+            .ok_or_else(|| QRError::new("No block info for this version/level"))?;
+
+        let mut message = vec![];
+        let mut errors_corrected = 0u32;
+        for ((mut block, erasures), info) in blocks
+            .into_iter()
+            .zip(block_erasures.into_iter())
+            .zip(info.iter())
+        {
+            let ec_cap = info.ec_cap as usize;
+            let erasure_positions: Vec<usize> = erasures
+                .iter()
+                .enumerate()
+                .filter(|&(_, &e)| e)
+                .map(|(i, _)| i)
+                .collect();
+            errors_corrected += correct_with_erasures(&mut block, ec_cap, &erasure_positions)? as u32;
+            message.extend_from_slice(&block[..block.len() - ec_cap]);
+        }
+
+        let mut reader = BitReader::new(&message);
+        let segments = decode_segments(&mut reader, data.version)?;
+        Ok((segments, errors_corrected))
+    }
+
+    /// As `segments_for`, but for a Micro QR symbol. Micro symbols are always
+    /// a single block, so there's no interleaving to undo; the codewords read
+    /// off the grid are corrected as one block and handed straight to the
+    /// Micro-specific bitstream reader.
+    fn micro_segments_for(&self, data: QRData) -> Result<(Vec<DecodedSegment>, u32), QRError> {
+        let level = if data.version == 1 {
+            None
+        } else {
+            Some(data.level)
+        };
+        let info = micro_block_info(data.version, level)
+            .ok_or_else(|| QRError::new("No block info for this Micro version/level"))?;
+        let block = &info[0];
+
+        let (mut codewords, erasures) = read_codewords(&data);
+        let ec_cap = block.ec_cap as usize;
+        let erasure_positions: Vec<usize> = erasures
+            .iter()
+            .enumerate()
+            .filter(|&(_, &e)| e)
+            .map(|(i, _)| i)
+            .collect();
+        let errors_corrected = if ec_cap > 0 {
+            correct_with_erasures(&mut codewords, ec_cap, &erasure_positions)? as u32
+        } else {
+            0
+        };
+        let message = &codewords[..codewords.len() - ec_cap];
+
+        let mut reader = BitReader::new(message);
+        let segments = decode_micro_segments(&mut reader, data.version)?;
+        Ok((segments, errors_corrected))
+    }
+}
+
+impl Decode for QRDecoder {
+    fn decode(&self, extraction: Vec<Result<QRData, QRError>>) -> Vec<Result<String, QRError>> {
+        extraction
+            .into_iter()
+            .map(|result| {
+                result
+                    .and_then(|data| self.segments_for(data))
+                    .map(|(segments, _)| segments.into_iter().map(|s| s.text).collect())
+            })
+            .collect()
+    }
+
+    fn decode_to_bytes(
+        &self,
+        extraction: Vec<Result<QRData, QRError>>,
+    ) -> Vec<Result<Vec<u8>, QRError>> {
+        extraction
+            .into_iter()
+            .map(|result| {
+                result
+                    .and_then(|data| self.segments_for(data))
+                    .map(|(segments, _)| {
+                        segments.into_iter().fold(vec![], |mut acc, s| {
+                            acc.extend(s.bytes);
+                            acc
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    fn decode_detailed(
+        &self,
+        extraction: Vec<(QRLocation, Result<QRData, QRError>)>,
+    ) -> Vec<Result<ScanResult, QRError>> {
+        extraction
+            .into_iter()
+            .map(|(location, result)| {
+                result.and_then(|data| {
+                    let (version, level, mask) = (data.version, data.level, data.mask);
+                    self.segments_for(data).map(|(segments, errors_corrected)| {
+                        let text = segments.into_iter().map(|s| s.text).collect();
+                        ScanResult::new(
+                            text,
+                            version,
+                            level,
+                            mask,
+                            location.top_left,
+                            location.top_right,
+                            location.bottom_left,
+                            location.module_size,
+                            errors_corrected,
+                        )
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// Whether `(row, col)` on a symbol of the given `version`/`side` belongs to a
+/// function pattern (finder, separator, format info, timing, dark module,
+/// alignment pattern or version info) rather than carrying codeword data.
+fn is_function_module(version: u32, side: u32, row: u32, col: u32) -> bool {
+    if (row < 9 && col < 9) || (row < 9 && col >= side - 8) || (row >= side - 8 && col < 9) {
+        return true;
+    }
+
+    if row == 6 || col == 6 {
+        return true;
+    }
+
+    if row == side - 8 && col == 8 {
+        return true;
+    }
+
+    if version >= 7 && ((row < 6 && col >= side - 11) || (col < 6 && row >= side - 11)) {
+        return true;
+    }
+
+    // Alignment patterns: 5x5 around every pair of centers from one evenly
+    // spaced list, except the three pairs that would overlap a finder.
+    if version >= 2 {
+        let count = version / 7 + 2;
+        let step = if version == 32 { 26 } else { (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2 };
+        let center = |i: u32| if i == 0 { 6 } else { side - 7 - (count - 1 - i) * step };
+        for i in 1..count * count {
+            let (ai, aj) = (i / count, i % count);
+            let overlaps_finder = (ai == 0 && aj == count - 1) || (ai == count - 1 && aj == 0);
+            let near = |c: u32, v: u32| (c as i64 - v as i64).abs() <= 2;
+            if !overlaps_finder && near(center(ai), row) && near(center(aj), col) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// As `is_function_module`, but for a Micro QR symbol: a single finder in the
+/// top-left corner (with no dark module or version info strips to account
+/// for), and a timing pattern along row/column 8 rather than 6.
+fn is_function_module_micro(row: u32, col: u32) -> bool {
+    (row < 9 && col < 9) || row == 8 || col == 8
+}
+
+/// Read codewords off the module grid in the spec's up/down zigzag over
+/// column pairs, starting at the bottom right and skipping the vertical
+/// timing pattern column and any function modules. Alongside the codeword
+/// bytes, returns a parallel erasure flag per codeword: `true` if any of the
+/// 8 modules packed into it was too unreliable to call confidently.
+fn read_codewords(data: &QRData) -> (Vec<u8>, Vec<bool>) {
+    let side = data.side as i64;
+    let skip_col = if data.micro { 8 } else { 6 };
+    let mut bits = vec![];
+    let mut erasure_bits = vec![];
+
+    let mut col = side - 1;
+    let mut going_up = true;
+
+    while col > 0 {
+        if col == skip_col {
+            col -= 1;
+        }
+
+        let rows: Vec<i64> = if going_up {
+            (0..side).rev().collect()
+        } else {
+            (0..side).collect()
+        };
+
+        for row in rows {
+            for c in 0..2 {
+                let cc = col - c;
+                let is_function = if data.micro {
+                    is_function_module_micro(row as u32, cc as u32)
+                } else {
+                    is_function_module(data.version, data.side, row as u32, cc as u32)
+                };
+                if !is_function {
+                    bits.push(data[[cc as u32, row as u32]]);
+                    erasure_bits.push(data.is_erasure(cc as u32, row as u32));
+                }
+            }
+        }
+
+        going_up = !going_up;
+        col -= 2;
+    }
+
+    (pack_bits(&bits), pack_erasures(&erasure_bits))
+}
+
+fn pack_bits(bits: &[u8]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|chunk| chunk.iter().fold(0u8, |acc, &b| (acc << 1) | (b & 1)))
+        .collect()
+}
+
+fn pack_erasures(erasure_bits: &[bool]) -> Vec<bool> {
+    erasure_bits
+        .chunks(8)
+        .map(|chunk| chunk.iter().any(|&e| e))
+        .collect()
+}
+
+/// Reads an MSB-first bitstream a fixed number of bits at a time.
+#[derive(Clone)]
+struct BitReader<'a> {
+    data: &'a [u8],
+    byte: usize,
+    bit: u8,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader {
+            data,
+            byte: 0,
+            bit: 0,
+        }
+    }
+
+    fn bits_left(&self) -> usize {
+        (self.data.len() - self.byte) * 8 - self.bit as usize
+    }
+
+    fn read(&mut self, count: u32) -> u32 {
+        let mut value = 0u32;
+        for _ in 0..count {
+            let byte = if self.byte < self.data.len() {
+                self.data[self.byte]
+            } else {
+                0
+            };
+            let bit = (byte >> (7 - self.bit)) & 1;
+            value = (value << 1) | bit as u32;
+
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.byte += 1;
+            }
+        }
+        value
+    }
+}
+
+/// Character count indicator widths for numeric/alphanumeric/byte/kanji modes,
+/// which depend on the symbol's version range (ISO/IEC 18004 Table 3).
+fn char_count_bits(mode: u32, version: u32) -> u32 {
+    match (mode, version) {
+        (0b0001, 1..=9) => 10,
+        (0b0001, _) if version <= 26 => 12,
+        (0b0001, _) => 14,
+        (0b0010, 1..=9) => 9,
+        (0b0010, _) if version <= 26 => 11,
+        (0b0010, _) => 13,
+        (0b0100, 1..=9) => 8,
+        (0b0100, _) => 16,
+        (0b1000, 1..=9) => 8,
+        (0b1000, _) if version <= 26 => 10,
+        (0b1000, _) => 12,
+        _ => 0,
+    }
+}
+
+/// Width in bits of a Micro QR symbol's mode indicator, which grows with the
+/// version since smaller symbols have fewer modes to distinguish between. M1
+/// has none at all: it only ever encodes a single numeric segment.
+fn micro_mode_bits(version: u32) -> u32 {
+    match version {
+        1 => 0,
+        2 => 1,
+        3 => 2,
+        _ => 3,
+    }
+}
+
+/// As `char_count_bits`, but for a Micro QR symbol (ISO/IEC 18004 Table 4).
+/// `mode` is the Micro mode indicator's value: 0 numeric, 1 alphanumeric,
+/// 2 byte, 3 kanji, unlike the standard symbol's 4-bit values above.
+fn micro_char_count_bits(mode: u32, version: u32) -> u32 {
+    match (mode, version) {
+        (0b00, 1) => 3,
+        (0b00, 2) => 4,
+        (0b00, 3) => 5,
+        (0b00, _) => 6,
+        (0b01, 2) => 3,
+        (0b01, 3) => 4,
+        (0b01, _) => 5,
+        (0b10, 3) => 4,
+        (0b10, _) => 5,
+        (0b11, 3) => 3,
+        (0b11, _) => 4,
+        _ => 0,
+    }
+}
+
+/// Length in bits of a Micro QR symbol's terminator, ISO/IEC 18004 Table 25.
+/// Unlike a standard symbol, there's no mode indicator value reserved to mark
+/// "end of message" - M2's mode indicator is only 1 bit wide, leaving no room
+/// for one - so the terminator is just this many zero bits appended straight
+/// after the last segment, with no indicator of its own.
+fn micro_terminator_bits(version: u32) -> u32 {
+    match version {
+        1 => 3,
+        2 => 5,
+        3 => 7,
+        _ => 9,
+    }
+}
+
+/// Whether `reader` has reached the end of the real segment data: the
+/// `micro_terminator_bits(version)` zero bits of the terminator, followed
+/// (once padded out to a byte boundary) by nothing but the standard
+/// `0xEC`/`0x11` pad codewords, or simply the end of the buffer.
+///
+/// This has to be checked structurally rather than by a reserved mode value,
+/// since - unlike a standard symbol's `mode == 0` - Micro QR's mode field is
+/// too narrow to reserve one: mode `0b00` is real numeric data at every
+/// version.
+fn at_micro_terminator(reader: &BitReader, version: u32) -> bool {
+    let terminator_bits = micro_terminator_bits(version);
+    if reader.bits_left() < terminator_bits as usize {
+        return true;
+    }
+
+    let mut probe = reader.clone();
+    if probe.read(terminator_bits) != 0 {
+        return false;
+    }
+
+    if probe.bit != 0 {
+        probe.read(8 - probe.bit as u32);
+    }
+
+    let mut expected = 0xECu8;
+    while probe.bits_left() >= 8 {
+        if probe.read(8) as u8 != expected {
+            return false;
+        }
+        expected = if expected == 0xEC { 0x11 } else { 0xEC };
+    }
+
+    true
+}
+
+/// As `decode_segments`, but for a Micro QR symbol's narrower mode indicator
+/// and character count fields, and smaller mode set (no ECI support).
+fn decode_micro_segments(
+    reader: &mut BitReader,
+    version: u32,
+) -> Result<Vec<DecodedSegment>, QRError> {
+    let mode_bits = micro_mode_bits(version);
+
+    if mode_bits == 0 {
+        let count = reader.read(micro_char_count_bits(0b00, version)) as usize;
+        return Ok(vec![decode_numeric(reader, count)]);
+    }
+
+    let mut segments = vec![];
+
+    loop {
+        if at_micro_terminator(reader, version) {
+            break;
+        }
+
+        let mode = reader.read(mode_bits);
+        let count_bits = micro_char_count_bits(mode, version);
+        if count_bits == 0 || reader.bits_left() < count_bits as usize {
+            break;
+        }
+
+        let count = reader.read(count_bits) as usize;
+
+        let segment = match mode {
+            0b00 => decode_numeric(reader, count),
+            0b01 => decode_alphanumeric(reader, count),
+            0b10 => decode_byte(reader, count, None),
+            _ => {
+                return Err(QRError::new(format!(
+                    "Unsupported Micro QR mode indicator {:02b}",
+                    mode
+                )))
+            }
+        };
+
+        segments.push(segment);
+    }
+
+    Ok(segments)
+}
+
+const ALPHANUMERIC_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+fn decode_segments(reader: &mut BitReader, version: u32) -> Result<Vec<DecodedSegment>, QRError> {
+    let mut segments = vec![];
+    // The ECI mode indicator sets the charset for byte-mode segments that
+    // follow it, until another ECI indicator changes it or the symbol ends.
+    let mut eci: Option<u32> = None;
+
+    loop {
+        if reader.bits_left() < 4 {
+            break;
+        }
+
+        let mode = reader.read(4);
+        if mode == 0 {
+            break;
+        }
+
+        if mode == 0b0111 {
+            eci = Some(read_eci_designator(reader));
+            continue;
+        }
+
+        let count = reader.read(char_count_bits(mode, version)) as usize;
+
+        let segment = match mode {
+            0b0001 => decode_numeric(reader, count),
+            0b0010 => decode_alphanumeric(reader, count),
+            0b0100 => decode_byte(reader, count, eci),
+            _ => {
+                return Err(QRError::new(format!(
+                    "Unsupported mode indicator {:04b}",
+                    mode
+                )))
+            }
+        };
+
+        segments.push(segment);
+    }
+
+    Ok(segments)
+}
+
+fn read_eci_designator(reader: &mut BitReader) -> u32 {
+    eci::read_eci_designator(|bits| reader.read(bits))
+}
+
+fn decode_numeric(reader: &mut BitReader, count: usize) -> DecodedSegment {
+    let mut text = String::new();
+    let mut remaining = count;
+    while remaining >= 3 {
+        text.push_str(&format!("{:03}", reader.read(10)));
+        remaining -= 3;
+    }
+    if remaining == 2 {
+        text.push_str(&format!("{:02}", reader.read(7)));
+    } else if remaining == 1 {
+        text.push_str(&format!("{}", reader.read(4)));
+    }
+
+    let bytes = text.clone().into_bytes();
+    DecodedSegment::new(Mode::Numeric, text, bytes, None)
+}
+
+fn decode_alphanumeric(reader: &mut BitReader, count: usize) -> DecodedSegment {
+    let mut text = String::new();
+    let mut remaining = count;
+    while remaining >= 2 {
+        let value = reader.read(11);
+        text.push(ALPHANUMERIC_CHARS[(value / 45) as usize] as char);
+        text.push(ALPHANUMERIC_CHARS[(value % 45) as usize] as char);
+        remaining -= 2;
+    }
+    if remaining == 1 {
+        let value = reader.read(6);
+        text.push(ALPHANUMERIC_CHARS[value as usize] as char);
+    }
+
+    let bytes = text.clone().into_bytes();
+    DecodedSegment::new(Mode::Alphanumeric, text, bytes, None)
+}
+
+fn decode_byte(reader: &mut BitReader, count: usize, eci: Option<u32>) -> DecodedSegment {
+    let mut bytes = Vec::with_capacity(count);
+    for _ in 0..count {
+        bytes.push(reader.read(8) as u8);
+    }
+
+    let charset = Charset::from_eci(eci.unwrap_or(26));
+    let text = charset.decode(&bytes);
+    DecodedSegment::new(Mode::Byte, text, bytes, eci)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// M3 numeric segment "123", its real 7-bit terminator, then standard
+    /// `EC 11 EC 11` pad codewords - the exact shape described as breaking
+    /// the old loop, which kept reading past the terminator into the pad
+    /// bytes as further mode+count fields.
+    #[test]
+    fn stops_at_the_terminator_instead_of_reading_into_pad_codewords() {
+        let message = [0x06, 0x3d, 0x80, 0xec, 0x11, 0xec, 0x11];
+        let mut reader = BitReader::new(&message);
+
+        let segments = decode_micro_segments(&mut reader, 3).unwrap();
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "123");
+    }
+
+    /// Same shape for M2, where the padding used to decode as a spurious
+    /// `Alphanumeric` segment rather than being recognised as padding.
+    #[test]
+    fn stops_at_the_terminator_for_m2() {
+        let message = [0x10, 0xc0, 0x00, 0xec, 0x11];
+        let mut reader = BitReader::new(&message);
+
+        let segments = decode_micro_segments(&mut reader, 2).unwrap();
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "12");
+    }
+}
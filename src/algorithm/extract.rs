@@ -0,0 +1,46 @@
+#[cfg(feature = "std")]
+use image::GrayImage;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use algorithm::sample::extract_one;
+use qr::{QRData, QRError, QRLocation};
+
+/// Samples a located symbol's modules into a `QRData` grid, ready for the
+/// decode stage.
+pub trait Extract<T> {
+    fn extract(&self, threshold: &T, locations: Vec<QRLocation>) -> Vec<Result<QRData, QRError>>;
+}
+
+/// Extracts a QR symbol by reading its module grid off the thresholded image,
+/// recovering the format information to determine the EC level and data
+/// mask, and undoing that mask before handing the grid on. Micro locations
+/// take the Micro format info and mask path instead.
+///
+/// The actual module-sampling logic lives in `algorithm::sample`, shared
+/// with `raw::RawExtractor` so a correctness fix to one doesn't silently
+/// miss the other; this just supplies the `image::GrayImage` to sample from.
+#[cfg(feature = "std")]
+pub struct QRExtractor {}
+
+#[cfg(feature = "std")]
+impl QRExtractor {
+    pub fn new() -> QRExtractor {
+        QRExtractor {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Extract<GrayImage> for QRExtractor {
+    fn extract(
+        &self,
+        threshold: &GrayImage,
+        locations: Vec<QRLocation>,
+    ) -> Vec<Result<QRData, QRError>> {
+        locations
+            .into_iter()
+            .map(|location| extract_one(threshold, location))
+            .collect()
+    }
+}
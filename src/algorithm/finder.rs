@@ -0,0 +1,385 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use algorithm::buffer::GrayBuffer;
+use point::Point;
+use qr::{QRFinderPosition, QRLocation};
+
+/// How far a measured run length, or a finder candidate's position/module
+/// size, may stray from its expected value and still be accepted - generous
+/// enough to tolerate anti-aliasing and the perspective skew of a
+/// non-perfectly-frontal photo.
+const TOLERANCE: f64 = 0.5;
+
+fn within(value: f64, expected: f64) -> bool {
+    expected > 0.0 && (value - expected).abs() <= expected * TOLERANCE
+}
+
+/// `f64::sqrt`/`round` pull in libm via `std`, which isn't available under
+/// `no_std` + `alloc`; this module needs both, so it rolls its own rather
+/// than duplicating the geometry below per build mode.
+fn sqrt(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+
+    let mut guess = value;
+    for _ in 0..20 {
+        guess = 0.5 * (guess + value / guess);
+    }
+    guess
+}
+
+fn round(value: f64) -> f64 {
+    let truncated = value as i64 as f64;
+    let fraction = value - truncated;
+    if fraction >= 0.5 {
+        truncated + 1.0
+    } else if fraction <= -0.5 {
+        truncated - 1.0
+    } else {
+        truncated
+    }
+}
+
+/// Run-length-encodes one row (`is_row`, scanning `x` at fixed `y`) or one
+/// column (scanning `y` at fixed `x`) of `buffer`, returning `(dark, length)`
+/// pairs in scan order.
+fn scan_runs<G: GrayBuffer>(buffer: &G, fixed: u32, length: u32, is_row: bool) -> Vec<(bool, u32)> {
+    let mut runs: Vec<(bool, u32)> = vec![];
+
+    for i in 0..length {
+        let (x, y) = if is_row { (i, fixed) } else { (fixed, i) };
+        let dark = buffer.get(x, y) < 128;
+
+        match runs.last_mut() {
+            Some(last) if last.0 == dark => last.1 += 1,
+            _ => runs.push((dark, 1)),
+        }
+    }
+
+    runs
+}
+
+/// Checks whether the 5 runs starting at `runs[start]` match a finder
+/// pattern's 1:1:3:1:1 module ratio (ISO/IEC 18004 7.3.2), and if so returns
+/// `(module_size, offset_to_center)` in pixels, `offset_to_center` being
+/// measured from the start of `runs[start]`.
+fn match_finder_ratio(runs: &[(bool, u32)], start: usize) -> Option<(f64, f64)> {
+    if start + 5 > runs.len() || !runs[start].0 {
+        return None;
+    }
+
+    let counts: Vec<f64> = runs[start..start + 5].iter().map(|&(_, n)| n as f64).collect();
+    let unit = (counts[0] + counts[1] + counts[3] + counts[4]) / 4.0;
+
+    if !within(counts[0], unit)
+        || !within(counts[1], unit)
+        || !within(counts[3], unit)
+        || !within(counts[4], unit)
+        || !within(counts[2], unit * 3.0)
+    {
+        return None;
+    }
+
+    let module_size = counts.iter().sum::<f64>() / 7.0;
+    let offset_to_center = counts[0] + counts[1] + counts[2] / 2.0;
+    Some((module_size, offset_to_center))
+}
+
+/// Scans every run of row `y` for a finder-pattern match, returning each
+/// candidate's `(center_x, module_size)`.
+fn scan_row<G: GrayBuffer>(buffer: &G, y: u32, width: u32) -> Vec<(f64, f64)> {
+    let runs = scan_runs(buffer, y, width, true);
+
+    let mut offset = 0u32;
+    let mut starts = Vec::with_capacity(runs.len());
+    for &(_, len) in &runs {
+        starts.push(offset);
+        offset += len;
+    }
+
+    let mut hits = vec![];
+    for (start, &run_start) in starts.iter().enumerate() {
+        if let Some((module_size, center_offset)) = match_finder_ratio(&runs, start) {
+            hits.push((run_start as f64 + center_offset, module_size));
+        }
+    }
+    hits
+}
+
+/// Confirms a horizontal finder candidate by re-scanning the column through
+/// its estimated center, returning the refined `(center_y, module_size)` if
+/// the column also matches the 1:1:3:1:1 ratio around `y`.
+fn confirm_column<G: GrayBuffer>(buffer: &G, x: f64, y: u32, height: u32) -> Option<(f64, f64)> {
+    let col = round(x).max(0.0) as u32;
+    let runs = scan_runs(buffer, col, height, false);
+
+    let mut offset = 0u32;
+    let mut starts = Vec::with_capacity(runs.len());
+    for &(_, len) in &runs {
+        starts.push(offset);
+        offset += len;
+    }
+
+    for (start, &run_start) in starts.iter().enumerate() {
+        if let Some((module_size, center_offset)) = match_finder_ratio(&runs, start) {
+            let run_end: u32 = run_start + runs[start..start + 5].iter().map(|&(_, n)| n).sum::<u32>();
+            if y >= run_start && y < run_end {
+                return Some((run_start as f64 + center_offset, module_size));
+            }
+        }
+    }
+    None
+}
+
+/// Merges `(x, y, module_size)` into `finders` if it's close enough to an
+/// existing entry to be another scan line crossing the same finder pattern,
+/// averaging the two; otherwise records it as a new candidate.
+fn merge_hit(finders: &mut Vec<QRFinderPosition>, x: f64, y: f64, module_size: f64) {
+    for finder in finders.iter_mut() {
+        let dx = finder.location.x - x;
+        let dy = finder.location.y - y;
+        if sqrt(dx * dx + dy * dy) < module_size * 2.0 {
+            finder.location = Point::new((finder.location.x + x) / 2.0, (finder.location.y + y) / 2.0);
+            finder.module_size = (finder.module_size + module_size) / 2.0;
+            return;
+        }
+    }
+    finders.push(QRFinderPosition {
+        location: Point::new(x, y),
+        module_size,
+    });
+}
+
+fn distance(a: &QRFinderPosition, b: &QRFinderPosition) -> f64 {
+    let dx = a.location.x - b.location.x;
+    let dy = a.location.y - b.location.y;
+    sqrt(dx * dx + dy * dy)
+}
+
+/// Checks whether three finder candidates form the right-angled top-left/
+/// top-right/bottom-left triple every standard QR symbol has (ISO/IEC 18004
+/// Figure 1), and if so builds the `QRLocation` for it. Order of `a`/`b`/`c`
+/// doesn't matter; the top-left corner is identified as the vertex whose two
+/// edges are of near-equal length and shorter than the diagonal between the
+/// other two.
+fn locate_triple(a: &QRFinderPosition, b: &QRFinderPosition, c: &QRFinderPosition) -> Option<QRLocation> {
+    let avg_module_size = (a.module_size + b.module_size + c.module_size) / 3.0;
+    if !within(a.module_size, avg_module_size)
+        || !within(b.module_size, avg_module_size)
+        || !within(c.module_size, avg_module_size)
+    {
+        return None;
+    }
+
+    let d_ab = distance(a, b);
+    let d_ac = distance(a, c);
+    let d_bc = distance(b, c);
+
+    let (top_left, leg_a, leg_b, diagonal) = if within(d_ab, d_ac) && d_ab < d_bc {
+        (a, b, c, d_bc)
+    } else if within(d_ab, d_bc) && d_ab < d_ac {
+        (b, a, c, d_ac)
+    } else if within(d_ac, d_bc) && d_ac < d_ab {
+        (c, a, b, d_ab)
+    } else {
+        return None;
+    };
+
+    if !within(diagonal, (distance(top_left, leg_a) + distance(top_left, leg_b)) / 2.0 * sqrt(2.0)) {
+        return None;
+    }
+
+    // In image coordinates (y grows downward), the top-right finder shares
+    // the top-left's y and the bottom-left finder shares its x.
+    let (top_right, bottom_left) = if (leg_a.location.y - top_left.location.y).abs()
+        < (leg_b.location.y - top_left.location.y).abs()
+    {
+        (leg_a, leg_b)
+    } else {
+        (leg_b, leg_a)
+    };
+
+    // Finder centers sit 3.5 modules in from the symbol's outer edge on both
+    // axes, so the module grid's origin is that far up and to the left of
+    // each one.
+    let corner = |finder: &QRFinderPosition| {
+        Point::new(
+            finder.location.x - 3.5 * avg_module_size,
+            finder.location.y - 3.5 * avg_module_size,
+        )
+    };
+
+    let modules_across = round((top_right.location.x - top_left.location.x).abs() / avg_module_size);
+    let side = modules_across + 7.0;
+    let version = (round((side - 17.0) / 4.0) as i64).clamp(1, 40) as u32;
+
+    Some(QRLocation {
+        top_left: corner(top_left),
+        top_right: corner(top_right),
+        bottom_left: corner(bottom_left),
+        module_size: avg_module_size,
+        version,
+        micro: false,
+    })
+}
+
+/// Groups confirmed finder candidates into the symbols they belong to,
+/// trying every 3-combination for a valid top-left/top-right/bottom-left
+/// triple. A candidate is used in at most one symbol.
+fn group_into_symbols(finders: &[QRFinderPosition]) -> Vec<QRLocation> {
+    let mut used = vec![false; finders.len()];
+    let mut locations = vec![];
+
+    for i in 0..finders.len() {
+        if used[i] {
+            continue;
+        }
+        for j in (i + 1)..finders.len() {
+            if used[j] {
+                continue;
+            }
+            for k in (j + 1)..finders.len() {
+                if used[k] {
+                    continue;
+                }
+                if let Some(location) = locate_triple(&finders[i], &finders[j], &finders[k]) {
+                    used[i] = true;
+                    used[j] = true;
+                    used[k] = true;
+                    locations.push(location);
+                }
+            }
+        }
+    }
+
+    locations
+}
+
+/// Locates standard QR symbols in `buffer` by scanning horizontal lines for
+/// the 1:1:3:1:1 module ratio of a finder pattern, confirming each match
+/// vertically, then grouping the confirmed finders into the top-left/
+/// top-right/bottom-left triples every symbol has. Shared by
+/// `locate::LineScan` and `raw::RawLineScan`.
+///
+/// Micro QR symbols have only a single finder pattern rather than three, so
+/// locating them needs a separate single-finder search this doesn't do.
+pub fn locate<G: GrayBuffer>(buffer: &G) -> Vec<QRLocation> {
+    let width = buffer.width();
+    let height = buffer.height();
+
+    let mut finders: Vec<QRFinderPosition> = vec![];
+
+    for y in 0..height {
+        for (x, module_size_h) in scan_row(buffer, y, width) {
+            if let Some((cy, module_size_v)) = confirm_column(buffer, x, y, height) {
+                merge_hit(&mut finders, x, cy, (module_size_h + module_size_v) / 2.0);
+            }
+        }
+    }
+
+    group_into_symbols(&finders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plain in-memory `GrayBuffer` for exercising `locate` against a
+    /// hand-drawn symbol without needing the `image` crate or an actual
+    /// PNG fixture.
+    struct TestImage {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    }
+
+    impl TestImage {
+        fn blank(width: u32, height: u32) -> TestImage {
+            TestImage {
+                width,
+                height,
+                pixels: vec![255; (width * height) as usize],
+            }
+        }
+
+        /// Draws one 7x7-module finder pattern (ISO/IEC 18004 7.3.2) with its
+        /// top-left module at pixel `(ox, oy)`, `module_size` pixels square.
+        fn draw_finder(&mut self, ox: u32, oy: u32, module_size: u32) {
+            for row in 0..7u32 {
+                for col in 0..7u32 {
+                    let dark = row == 0 || row == 6 || col == 0 || col == 6 || (2..=4).contains(&row) && (2..=4).contains(&col);
+                    let value = if dark { 0 } else { 255 };
+                    for py in 0..module_size {
+                        for px in 0..module_size {
+                            let x = ox + col * module_size + px;
+                            let y = oy + row * module_size + py;
+                            self.set(x, y, value);
+                        }
+                    }
+                }
+            }
+        }
+
+        fn set(&mut self, x: u32, y: u32, value: u8) {
+            self.pixels[(y * self.width + x) as usize] = value;
+        }
+    }
+
+    impl GrayBuffer for TestImage {
+        fn width(&self) -> u32 {
+            self.width
+        }
+
+        fn height(&self) -> u32 {
+            self.height
+        }
+
+        fn get(&self, x: u32, y: u32) -> u8 {
+            self.pixels[(y * self.width + x) as usize]
+        }
+    }
+
+    /// A version 1 symbol (side 21) at 4px/module with its three finders
+    /// drawn in the right places; `locate` should recover one `QRLocation`
+    /// whose corners and version match what was drawn.
+    #[test]
+    fn locates_a_version_1_symbols_three_finders() {
+        let module_size = 4u32;
+        let margin = 4 * module_size;
+        let side = 21u32;
+        let image_size = margin * 2 + side * module_size;
+
+        let mut image = TestImage::blank(image_size, image_size);
+        let top_left_px = (margin, margin);
+        let top_right_px = (margin + (side - 7) * module_size, margin);
+        let bottom_left_px = (margin, margin + (side - 7) * module_size);
+
+        image.draw_finder(top_left_px.0, top_left_px.1, module_size);
+        image.draw_finder(top_right_px.0, top_right_px.1, module_size);
+        image.draw_finder(bottom_left_px.0, bottom_left_px.1, module_size);
+
+        let locations = locate(&image);
+
+        assert_eq!(locations.len(), 1);
+        let location = &locations[0];
+        assert_eq!(location.version, 1);
+        assert!(!location.micro);
+        assert!((location.module_size - module_size as f64).abs() < 0.5);
+        assert!((location.top_left.x - margin as f64).abs() < 1.0);
+        assert!((location.top_left.y - margin as f64).abs() < 1.0);
+        assert!((location.top_right.x - top_right_px.0 as f64).abs() < 1.0);
+        assert!((location.bottom_left.y - bottom_left_px.1 as f64).abs() < 1.0);
+    }
+
+    /// No finder patterns anywhere in the image: `locate` should come back
+    /// empty rather than hallucinating a symbol.
+    #[test]
+    fn finds_nothing_in_a_blank_image() {
+        let image = TestImage::blank(64, 64);
+        assert!(locate(&image).is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(feature = "std")]
+use image::DynamicImage;
+#[cfg(feature = "std")]
+use image::GenericImageView;
+#[cfg(feature = "std")]
+use image::GrayImage;
+#[cfg(feature = "std")]
+use image::Luma;
+#[cfg(feature = "std")]
+use image::Pixel;
+
+/// Converts a source image of type `S` into a single-channel grayscale image of type `G`.
+pub trait Grayscale<S, G> {
+    fn to_grayscale(&self, source: &S) -> G;
+}
+
+/// Converts images to grayscale using the luma (perceptual brightness) channel.
+#[cfg(feature = "std")]
+pub struct ToLuma {}
+
+#[cfg(feature = "std")]
+impl ToLuma {
+    pub fn new() -> ToLuma {
+        ToLuma {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Grayscale<DynamicImage, GrayImage> for ToLuma {
+    fn to_grayscale(&self, source: &DynamicImage) -> GrayImage {
+        let (width, height) = source.dimensions();
+        let mut gray = GrayImage::new(width, height);
+
+        for (x, y, pixel) in source.pixels() {
+            let luma = pixel.to_luma();
+            gray.put_pixel(x, y, Luma([luma[0]]));
+        }
+
+        gray
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "std")]
+use image::GrayImage;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use algorithm::finder;
+use qr::QRLocation;
+
+/// Finds candidate QR symbols within a thresholded image of type `T`.
+pub trait Locate<T> {
+    fn locate(&self, threshold: &T) -> Vec<QRLocation>;
+}
+
+/// Locates QR symbols by scanning horizontal lines for the 1:1:3:1:1 module ratio
+/// of a finder pattern, then confirming the match vertically and grouping three
+/// finders into the top-left/top-right/bottom-left triple every QR symbol has.
+///
+/// Micro QR symbols have only a single finder pattern rather than three, so
+/// grouping them into a `QRLocation` with `micro: true` needs a separate
+/// single-finder search; `LineScan` doesn't implement that yet.
+#[cfg(feature = "std")]
+pub struct LineScan {}
+
+#[cfg(feature = "std")]
+impl LineScan {
+    pub fn new() -> LineScan {
+        LineScan {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Locate<GrayImage> for LineScan {
+    fn locate(&self, threshold: &GrayImage) -> Vec<QRLocation> {
+        finder::locate(threshold)
+    }
+}
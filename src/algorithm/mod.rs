@@ -0,0 +1,10 @@
+mod buffer;
+pub mod decode;
+pub mod extract;
+mod finder;
+pub mod grayscale;
+pub mod locate;
+#[cfg(not(feature = "std"))]
+pub mod raw;
+mod sample;
+pub mod threshold;
@@ -0,0 +1,170 @@
+//! Raw-buffer equivalents of the `image`-backed `Grayscale`/`Threshold`/
+//! `Locate`/`Extract` components, for `no_std` builds that have no `image`
+//! crate to draw on. A caller on an embedded target captures a frame into a
+//! `Vec<u8>` luma buffer itself and wraps it in a `RawGray` instead of handing
+//! a `DynamicImage`/`GrayImage` to the `std` components.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use algorithm::extract::Extract;
+use algorithm::finder;
+use algorithm::grayscale::Grayscale;
+use algorithm::locate::Locate;
+use algorithm::sample::extract_one;
+use algorithm::threshold::Threshold;
+use qr::{QRData, QRError, QRLocation};
+
+/// A single-channel grayscale buffer backed by a caller-owned `Vec<u8>`
+/// rather than `image::GrayImage`, so it works without the `image` crate.
+#[derive(Debug, Clone)]
+pub struct RawGray {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl RawGray {
+    /// `pixels` must hold exactly `width * height` entries, row-major.
+    pub fn new(pixels: Vec<u8>, width: u32, height: u32) -> RawGray {
+        RawGray {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub(crate) fn get(&self, x: u32, y: u32) -> u8 {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    fn set(&mut self, x: u32, y: u32, value: u8) {
+        self.pixels[(y * self.width + x) as usize] = value;
+    }
+}
+
+/// Passes a caller-supplied `RawGray` buffer through unchanged: without the
+/// `image` crate there's no separate color source type to convert from, so
+/// the caller is expected to have captured luma values directly.
+pub struct Identity {}
+
+impl Identity {
+    pub fn new() -> Identity {
+        Identity {}
+    }
+}
+
+impl Grayscale<RawGray, RawGray> for Identity {
+    fn to_grayscale(&self, source: &RawGray) -> RawGray {
+        source.clone()
+    }
+}
+
+/// As `threshold::BlockedMean`, but operating on a `RawGray` buffer instead
+/// of an `image::GrayImage`.
+pub struct RawBlockedMean {
+    block_size: u32,
+    tolerance: u32,
+}
+
+impl RawBlockedMean {
+    pub fn new(block_size: u32, tolerance: u32) -> RawBlockedMean {
+        RawBlockedMean {
+            block_size,
+            tolerance,
+        }
+    }
+}
+
+impl Threshold<RawGray, RawGray> for RawBlockedMean {
+    fn to_threshold(&self, grayscale: RawGray) -> RawGray {
+        let (width, height) = (grayscale.width, grayscale.height);
+        let mut out = RawGray::new(vec![0u8; (width * height) as usize], width, height);
+
+        let half = (self.block_size / 2).max(1);
+
+        for y in 0..height {
+            for x in 0..width {
+                let x0 = x.saturating_sub(half);
+                let y0 = y.saturating_sub(half);
+                let x1 = (x + half).min(width - 1);
+                let y1 = (y + half).min(height - 1);
+
+                let mut sum = 0u32;
+                let mut count = 0u32;
+                for by in y0..=y1 {
+                    for bx in x0..=x1 {
+                        sum += grayscale.get(bx, by) as u32;
+                        count += 1;
+                    }
+                }
+
+                let mean = sum / count.max(1);
+                let pixel = grayscale.get(x, y) as i64;
+                let diff = pixel - mean as i64;
+                let value = if diff.abs() as u32 <= self.tolerance {
+                    128
+                } else if diff < 0 {
+                    0
+                } else {
+                    255
+                };
+                out.set(x, y, value as u8);
+            }
+        }
+
+        out
+    }
+}
+
+/// As `locate::LineScan`, but for `RawGray` buffers. Shares its finder-pattern
+/// scanning and triple-grouping with `LineScan` via `algorithm::finder`, same
+/// as `RawExtractor`/`QRExtractor` share their module-sampling logic.
+pub struct RawLineScan {}
+
+impl RawLineScan {
+    pub fn new() -> RawLineScan {
+        RawLineScan {}
+    }
+}
+
+impl Locate<RawGray> for RawLineScan {
+    fn locate(&self, threshold: &RawGray) -> Vec<QRLocation> {
+        finder::locate(threshold)
+    }
+}
+
+/// As `extract::QRExtractor`, but sampling modules out of a `RawGray` buffer
+/// instead of an `image::GrayImage`.
+///
+/// The actual module-sampling logic lives in `algorithm::sample`, shared
+/// with `QRExtractor` so a correctness fix to one doesn't silently miss the
+/// other; this just supplies the `RawGray` to sample from.
+pub struct RawExtractor {}
+
+impl RawExtractor {
+    pub fn new() -> RawExtractor {
+        RawExtractor {}
+    }
+}
+
+impl Extract<RawGray> for RawExtractor {
+    fn extract(
+        &self,
+        threshold: &RawGray,
+        locations: Vec<QRLocation>,
+    ) -> Vec<Result<QRData, QRError>> {
+        locations
+            .into_iter()
+            .map(|location| extract_one(threshold, location))
+            .collect()
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use algorithm::buffer::GrayBuffer;
+use qr::format::{
+    apply_mask, apply_micro_mask, decode_format_bits, decode_micro_format_bits, ECLevel,
+};
+use qr::{block_info, micro_block_info};
+use qr::{QRData, QRError, QRLocation};
+
+/// Columns/rows (relative to the symbol, 0-indexed) that make up the first
+/// copy of the 15-bit format info field, bit 14 (MSB) first.
+fn format_coordinates_copy_a() -> Vec<(u32, u32)> {
+    let mut coords = vec![];
+    for col in &[0u32, 1, 2, 3, 4, 5, 7, 8] {
+        coords.push((8, *col));
+    }
+    for row in &[7u32, 5, 4, 3, 2, 1, 0] {
+        coords.push((*row, 8));
+    }
+    coords
+}
+
+/// Coordinates (relative to the symbol, 0-indexed) of a Micro QR symbol's
+/// single copy of its 15-bit format info field, bit 14 (MSB) first: 8
+/// modules along the row next to the finder, then 7 down the column next
+/// to it.
+fn micro_format_coordinates() -> Vec<(u32, u32)> {
+    let mut coords = vec![];
+    for col in (1..=8u32).rev() {
+        coords.push((8, col));
+    }
+    for row in 1..=7u32 {
+        coords.push((row, 8));
+    }
+    coords
+}
+
+fn sample<G: GrayBuffer>(buffer: &G, location: &QRLocation, row: u32, col: u32) -> u8 {
+    let x = location.top_left.x + (col as f64 + 0.5) * location.module_size;
+    let y = location.top_left.y + (row as f64 + 0.5) * location.module_size;
+    buffer.get(x as u32, y as u32)
+}
+
+fn is_dark<G: GrayBuffer>(buffer: &G, location: &QRLocation, row: u32, col: u32) -> bool {
+    sample(buffer, location, row, col) < 128
+}
+
+/// Whether the module's sampled value was too close to the local threshold
+/// to call confidently (see `threshold::BlockedMean`).
+fn is_erasure<G: GrayBuffer>(buffer: &G, location: &QRLocation, row: u32, col: u32) -> bool {
+    sample(buffer, location, row, col) == 128
+}
+
+/// Extracts a standard QR symbol by reading its module grid off `buffer`,
+/// recovering the format information to determine the EC level and data
+/// mask, and undoing that mask before handing the grid on; a Micro location
+/// is handed to `extract_one_micro` instead. Shared by
+/// `extract::QRExtractor` and `raw::RawExtractor`, which only differ in
+/// which `GrayBuffer` they sample from.
+pub fn extract_one<G: GrayBuffer>(buffer: &G, location: QRLocation) -> Result<QRData, QRError> {
+    if location.micro {
+        return extract_one_micro(buffer, location);
+    }
+
+    let side = location.version * 4 + 17;
+
+    let mut bits: u16 = 0;
+    for (row, col) in format_coordinates_copy_a() {
+        bits <<= 1;
+        if is_dark(buffer, &location, row, col) {
+            bits |= 1;
+        }
+    }
+
+    let format_info = decode_format_bits(bits)
+        .ok_or_else(|| QRError::new("Could not recover format information"))?;
+
+    if block_info(location.version, format_info.level).is_none() {
+        return Err(QRError::new("No block layout for this version/level"));
+    }
+
+    let mut data = Vec::with_capacity((side * side) as usize);
+    let mut erasures = Vec::with_capacity((side * side) as usize);
+    for row in 0..side {
+        for col in 0..side {
+            let dark = is_dark(buffer, &location, row, col);
+            let dark = dark ^ apply_mask(format_info.mask, row, col);
+            // `QRData`'s indexing reads a stored 0 as a dark (1) bit.
+            data.push(if dark { 0 } else { 1 });
+            erasures.push(is_erasure(buffer, &location, row, col));
+        }
+    }
+
+    Ok(QRData::new(
+        data,
+        location.version,
+        format_info.level,
+        format_info.mask,
+        erasures,
+        false,
+    ))
+}
+
+/// As `extract_one`, but for a Micro QR symbol: the format info is read off
+/// the single copy next to its one finder pattern, and its version and EC
+/// level come folded together out of the format info itself rather than
+/// being known up front by the locate stage.
+pub fn extract_one_micro<G: GrayBuffer>(
+    buffer: &G,
+    location: QRLocation,
+) -> Result<QRData, QRError> {
+    let mut bits: u16 = 0;
+    for (row, col) in micro_format_coordinates() {
+        bits <<= 1;
+        if is_dark(buffer, &location, row, col) {
+            bits |= 1;
+        }
+    }
+
+    let format_info = decode_micro_format_bits(bits)
+        .ok_or_else(|| QRError::new("Could not recover Micro QR format information"))?;
+
+    if micro_block_info(format_info.version, format_info.level).is_none() {
+        return Err(QRError::new("No block layout for this Micro version/level"));
+    }
+
+    let side = format_info.version * 2 + 9;
+
+    let mut data = Vec::with_capacity((side * side) as usize);
+    let mut erasures = Vec::with_capacity((side * side) as usize);
+    for row in 0..side {
+        for col in 0..side {
+            let dark = is_dark(buffer, &location, row, col);
+            let dark = dark ^ apply_micro_mask(format_info.mask, row, col);
+            // `QRData`'s indexing reads a stored 0 as a dark (1) bit.
+            data.push(if dark { 0 } else { 1 });
+            erasures.push(is_erasure(buffer, &location, row, col));
+        }
+    }
+
+    let level = format_info.level.unwrap_or(ECLevel::LOW);
+    Ok(QRData::new(
+        data,
+        format_info.version,
+        level,
+        format_info.mask,
+        erasures,
+        true,
+    ))
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "std")]
+use image::GrayImage;
+
+/// Converts a grayscale image of type `G` into a black/white image of type `T`.
+pub trait Threshold<G, T> {
+    fn to_threshold(&self, grayscale: G) -> T;
+}
+
+/// Thresholds an image by comparing each pixel to the mean brightness of the
+/// `block_size x block_size` block of pixels around it, rather than a single
+/// global threshold, so the decoder tolerates uneven lighting across the symbol.
+///
+/// Pixels within `tolerance` of their local mean are too close to call; rather
+/// than guessing, those are written out as `128` (neither the `0` nor `255` a
+/// confident black/white decision would produce) so later stages can flag them
+/// as erasures instead of trusting a coin flip.
+#[cfg(feature = "std")]
+pub struct BlockedMean {
+    block_size: u32,
+    tolerance: u32,
+}
+
+#[cfg(feature = "std")]
+impl BlockedMean {
+    pub fn new(block_size: u32, tolerance: u32) -> BlockedMean {
+        BlockedMean {
+            block_size,
+            tolerance,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Threshold<GrayImage, GrayImage> for BlockedMean {
+    fn to_threshold(&self, grayscale: GrayImage) -> GrayImage {
+        let (width, height) = grayscale.dimensions();
+        let mut out = GrayImage::new(width, height);
+
+        let half = (self.block_size / 2).max(1);
+
+        for y in 0..height {
+            for x in 0..width {
+                let x0 = x.saturating_sub(half);
+                let y0 = y.saturating_sub(half);
+                let x1 = (x + half).min(width - 1);
+                let y1 = (y + half).min(height - 1);
+
+                let mut sum = 0u32;
+                let mut count = 0u32;
+                for by in y0..=y1 {
+                    for bx in x0..=x1 {
+                        sum += grayscale.get_pixel(bx, by)[0] as u32;
+                        count += 1;
+                    }
+                }
+
+                let mean = sum / count.max(1);
+                let pixel = grayscale.get_pixel(x, y)[0] as i64;
+                let diff = pixel - mean as i64;
+                let value = if diff.abs() as u32 <= self.tolerance {
+                    128
+                } else if diff < 0 {
+                    0
+                } else {
+                    255
+                };
+                out.put_pixel(x, y, image::Luma([value as u8]));
+            }
+        }
+
+        out
+    }
+}
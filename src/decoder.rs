@@ -1,18 +1,34 @@
+#[cfg(feature = "std")]
 use image::DynamicImage;
+#[cfg(feature = "std")]
 use image::GrayImage;
 
-use algorithm::decode::Decode;
-use algorithm::decode::QRDecoder;
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use algorithm::decode::{Decode, QRDecoder};
 use algorithm::extract::Extract;
+#[cfg(feature = "std")]
 use algorithm::extract::QRExtractor;
 use algorithm::grayscale::Grayscale;
+#[cfg(feature = "std")]
 use algorithm::grayscale::ToLuma;
+#[cfg(feature = "std")]
 use algorithm::locate::LineScan;
 use algorithm::locate::Locate;
+#[cfg(not(feature = "std"))]
+use algorithm::raw::{Identity, RawBlockedMean, RawExtractor, RawGray, RawLineScan};
+#[cfg(feature = "std")]
 use algorithm::threshold::BlockedMean;
 use algorithm::threshold::Threshold;
 
-use qr::QRError;
+use qr::{QRData, QRError, QRLocation, ScanResult};
 
 pub struct Decoder<S, G, T> {
     grayscale: Box<Grayscale<S, G>>,
@@ -24,16 +40,55 @@ pub struct Decoder<S, G, T> {
 
 impl<S, G, T> Decoder<S, G, T> {
     pub fn decode(&self, source: &S) -> Vec<Result<String, QRError>> {
+        match self.locate_and_extract(source) {
+            Some(extraction) => self.decode.decode(extraction),
+            None => vec![],
+        }
+    }
+
+    /// As `decode`, but returns each symbol's raw decoded bytes rather than a
+    /// `String`, so byte-mode segments that aren't valid UTF-8 aren't lossily
+    /// converted.
+    pub fn decode_to_bytes(&self, source: &S) -> Vec<Result<Vec<u8>, QRError>> {
+        match self.locate_and_extract(source) {
+            Some(extraction) => self.decode.decode_to_bytes(extraction),
+            None => vec![],
+        }
+    }
+
+    /// As `decode`, but returns a `ScanResult` per symbol carrying its
+    /// finder geometry, version, EC level, mask and Reed-Solomon error count
+    /// alongside the decoded text, instead of throwing that metadata away.
+    pub fn decode_detailed(&self, source: &S) -> Vec<Result<ScanResult, QRError>> {
+        match self.locate_and_extract_with_locations(source) {
+            Some(extraction) => self.decode.decode_detailed(extraction),
+            None => vec![],
+        }
+    }
+
+    fn locate_and_extract(&self, source: &S) -> Option<Vec<Result<QRData, QRError>>> {
+        self.locate_and_extract_with_locations(source)
+            .map(|pairs| pairs.into_iter().map(|(_, result)| result).collect())
+    }
+
+    /// As `locate_and_extract`, but keeps each extraction paired with the
+    /// `QRLocation` it came from, for `decode_detailed`'s metadata.
+    fn locate_and_extract_with_locations(
+        &self,
+        source: &S,
+    ) -> Option<Vec<(QRLocation, Result<QRData, QRError>)>> {
         let grayscale = self.grayscale.to_grayscale(source);
         let threshold = self.threshold.to_threshold(grayscale);
         let locations = self.locate.locate(&threshold);
 
         if locations.len() == 0 {
-            return vec![];
+            return None;
         }
 
+        let locations_for_result = locations.clone();
         let extraction = self.extract.extract(&threshold, locations);
-        self.decode.decode(extraction)
+
+        Some(locations_for_result.into_iter().zip(extraction).collect())
     }
 }
 
@@ -48,6 +103,7 @@ impl<S, G, T> Decoder<S, G, T> {
 /// * decode: QRDecoder
 ///
 /// This is meant to provide a good balance between speed and accuracy
+#[cfg(feature = "std")]
 pub fn default_decoder() -> Decoder<DynamicImage, GrayImage, GrayImage> {
     default_builder().build()
 }
@@ -155,6 +211,7 @@ impl<S, G, T> DecoderBuilder<S, G, T> {
 /// * decode: QRDecoder
 ///
 /// The builder can then be customised before creating the Decoder
+#[cfg(feature = "std")]
 pub fn default_builder() -> DecoderBuilder<DynamicImage, GrayImage, GrayImage> {
     let mut db = DecoderBuilder::new();
 
@@ -166,3 +223,30 @@ pub fn default_builder() -> DecoderBuilder<DynamicImage, GrayImage, GrayImage> {
 
     db
 }
+
+/// As `default_decoder`, but for `no_std` builds: takes a caller-captured
+/// raw luma buffer (`RawGray`) rather than an `image::DynamicImage`.
+#[cfg(not(feature = "std"))]
+pub fn raw_decoder() -> Decoder<RawGray, RawGray, RawGray> {
+    raw_builder().build()
+}
+
+/// As `default_builder`, but wires up the `no_std`-friendly components:
+///
+/// * grayscale: Identity
+/// * threshold: RawBlockedMean
+/// * locate: RawLineScan
+/// * extract: RawExtractor
+/// * decode: QRDecoder
+#[cfg(not(feature = "std"))]
+pub fn raw_builder() -> DecoderBuilder<RawGray, RawGray, RawGray> {
+    let mut db = DecoderBuilder::new();
+
+    db.grayscale(Box::new(Identity::new()));
+    db.threshold(Box::new(RawBlockedMean::new(5, 7)));
+    db.locate(Box::new(RawLineScan::new()));
+    db.extract(Box::new(RawExtractor::new()));
+    db.decode(Box::new(QRDecoder::new()));
+
+    db
+}
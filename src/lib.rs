@@ -0,0 +1,27 @@
+//! By default this crate links the standard library, which `Decoder`'s
+//! bundled `image`-backed components need. Building with `--no-default-features`
+//! drops that link and pulls in `alloc` instead, for embedded/bare-metal
+//! targets (handheld scanners, firmware) that only have a heap allocator:
+//! `QRError` still implements `Display`/`Debug` via `core::fmt`, and
+//! `algorithm::raw::RawGray` gives the `Grayscale`/`Threshold`/`Extract`
+//! stages a caller-supplied `Vec<u8>` buffer + dimensions to run against
+//! instead of `image::DynamicImage`/`GrayImage`. `Cargo.toml` declares a
+//! default-on `std` feature for exactly this purpose, so the gate below is
+//! live in both build modes.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate image;
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+pub mod algorithm;
+pub mod decoder;
+pub mod point;
+pub mod qr;
+
+#[cfg(feature = "std")]
+pub use decoder::{default_builder, default_decoder, Decoder, DecoderBuilder};
+#[cfg(not(feature = "std"))]
+pub use decoder::{raw_builder, raw_decoder, Decoder, DecoderBuilder};
@@ -0,0 +1,74 @@
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use qr::format::ECLevel;
+use qr::{block_info, QRError};
+
+/// Split the raw codeword stream read off a symbol into its error-correction blocks
+/// and de-interleave it back into per-block order.
+///
+/// QR symbols interleave the codewords of all their blocks column by column so that
+/// a burst of physical damage is spread across several blocks rather than destroying
+/// one outright. To undo that, this reads one codeword from each block in turn
+/// (group 1 blocks first, then group 2 blocks) until every block has its full
+/// complement of codewords, then does the same again for the EC codewords appended
+/// after the data.
+///
+/// `erasures` is a per-codeword confidence flag, parallel to `codewords` (`true`
+/// meaning the extractor couldn't read that codeword's modules reliably); it is
+/// de-interleaved alongside the codewords themselves so each block knows which of
+/// its own codewords are erasures rather than ordinary unknown errors.
+pub fn deinterleave(
+    codewords: &[u8],
+    erasures: &[bool],
+    version: u32,
+    level: ECLevel,
+) -> Result<(Vec<Vec<u8>>, Vec<Vec<bool>>), QRError> {
+    let info = block_info(version, level).ok_or_else(|| {
+        QRError::new(format!(
+            "No block info for version {} level {:?}",
+            version, level
+        ))
+    })?;
+
+    let block_count = info.len();
+    let max_data = info.iter().map(|b| b.data_per).max().unwrap_or(0) as usize;
+    let ec_cap = info[0].ec_cap as usize;
+
+    let mut blocks: Vec<Vec<u8>> = info
+        .iter()
+        .map(|b| Vec::with_capacity(b.total_per as usize))
+        .collect();
+    let mut block_erasures: Vec<Vec<bool>> = info
+        .iter()
+        .map(|b| Vec::with_capacity(b.total_per as usize))
+        .collect();
+
+    let mut pos = 0;
+
+    // Data codewords: read one column at a time; shorter blocks simply run out
+    // of data a column early and are skipped for the remaining columns.
+    for col in 0..max_data {
+        for (i, block) in info.iter().enumerate() {
+            if col < block.data_per as usize {
+                blocks[i].push(codewords[pos]);
+                block_erasures[i].push(erasures.get(pos).cloned().unwrap_or(false));
+                pos += 1;
+            }
+        }
+    }
+
+    // EC codewords: every block in a symbol carries the same number, so this is
+    // a plain column-major read across all blocks.
+    for _ in 0..ec_cap {
+        for i in 0..block_count {
+            blocks[i].push(codewords[pos]);
+            block_erasures[i].push(erasures.get(pos).cloned().unwrap_or(false));
+            pos += 1;
+        }
+    }
+
+    Ok((blocks, block_erasures))
+}
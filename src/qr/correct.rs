@@ -0,0 +1,387 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use qr::QRError;
+
+/// GF(256) arithmetic for the Reed-Solomon codes QR symbols use, built over
+/// the primitive polynomial 0x11D (x^8 + x^4 + x^3 + x^2 + 1) with generator 2.
+struct GaloisField {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl GaloisField {
+    fn new() -> GaloisField {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+
+        let mut x: u16 = 1;
+        for i in 0..255 {
+            exp[i] = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11D;
+            }
+        }
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+
+        GaloisField { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+        }
+    }
+
+    fn inv(&self, a: u8) -> u8 {
+        self.exp[255 - self.log[a as usize] as usize]
+    }
+
+    fn pow(&self, a: u8, power: usize) -> u8 {
+        if a == 0 {
+            return 0;
+        }
+        self.exp[(self.log[a as usize] as usize * power) % 255]
+    }
+}
+
+/// Evaluate polynomial `coeffs` (highest-degree coefficient first) at `x` over GF(256).
+/// Used only for reading syndromes directly off the codeword bytes, which are
+/// naturally stored most-significant-byte first.
+fn eval(gf: &GaloisField, coeffs: &[u8], x: u8) -> u8 {
+    coeffs.iter().fold(0, |acc, &c| gf.mul(acc, x) ^ c)
+}
+
+/// Evaluate polynomial `coeffs` (ascending degree, `coeffs[0]` is the x^0
+/// term) at `x` over GF(256). The rest of this module's Reed-Solomon math
+/// (locator/evaluator polynomials, Berlekamp-Massey) is kept in this order
+/// since it's the natural one for convolution and truncation mod x^k.
+fn poly_eval(gf: &GaloisField, coeffs: &[u8], x: u8) -> u8 {
+    let mut result = 0u8;
+    let mut xp = 1u8;
+    for &c in coeffs {
+        result ^= gf.mul(c, xp);
+        xp = gf.mul(xp, x);
+    }
+    result
+}
+
+/// Multiply two GF(256) polynomials, ascending degree order.
+fn poly_mul(gf: &GaloisField, a: &[u8], b: &[u8]) -> Vec<u8> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut out = vec![0u8; a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        if ai == 0 {
+            continue;
+        }
+        for (j, &bj) in b.iter().enumerate() {
+            out[i + j] ^= gf.mul(ai, bj);
+        }
+    }
+    out
+}
+
+/// Add (XOR) two GF(256) polynomials, ascending degree order.
+fn poly_add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; a.len().max(b.len())];
+    for (i, &c) in a.iter().enumerate() {
+        out[i] = c;
+    }
+    for (i, &c) in b.iter().enumerate() {
+        out[i] ^= c;
+    }
+    out
+}
+
+/// The erasure locator polynomial `product(1 + alpha^j * x)` over the
+/// reversed positions of the known erasures, whose roots are exactly the
+/// erased codewords' locations. Folding this into the final error locator
+/// lets the decoder treat those positions as known-bad without spending a
+/// syndrome degree of freedom discovering them.
+fn erasure_locator(gf: &GaloisField, reversed_positions: &[usize]) -> Vec<u8> {
+    let mut loc = vec![1u8];
+    for &j in reversed_positions {
+        loc = poly_mul(gf, &loc, &[1, gf.pow(2, j)]);
+    }
+    loc
+}
+
+/// Berlekamp-Massey: the shortest-degree polynomial `lambda` (ascending,
+/// `lambda[0] == 1`) satisfying the linear recurrence `sum(lambda[j] *
+/// s[i - j]) == 0` for every `i`, over the syndrome-like sequence `s`.
+fn berlekamp_massey(gf: &GaloisField, s: &[u8]) -> Vec<u8> {
+    let mut lambda = vec![1u8];
+    let mut prev = vec![1u8];
+    let mut gap = 1;
+    let mut prev_discrepancy = 1u8;
+
+    for i in 0..s.len() {
+        let mut discrepancy = s[i];
+        for j in 1..lambda.len() {
+            discrepancy ^= gf.mul(lambda[j], s[i - j]);
+        }
+
+        if discrepancy == 0 {
+            gap += 1;
+            continue;
+        }
+
+        let scale = gf.mul(discrepancy, gf.inv(prev_discrepancy));
+        let mut shifted = vec![0u8; gap];
+        shifted.extend(prev.iter().map(|&c| gf.mul(c, scale)));
+
+        if 2 * (lambda.len() - 1) <= i {
+            let next = poly_add(&lambda, &shifted);
+            prev = lambda;
+            lambda = next;
+            prev_discrepancy = discrepancy;
+            gap = 1;
+        } else {
+            lambda = poly_add(&lambda, &shifted);
+            gap += 1;
+        }
+    }
+
+    lambda
+}
+
+/// Correct up to `ec_len / 2` byte errors in `codewords` in place (the last
+/// `ec_len` bytes are the Reed-Solomon EC codewords), returning the number of
+/// errors corrected. This bound is the real Reed-Solomon minimum-distance
+/// guarantee, not just a best effort: with no erasures the Forney syndromes
+/// in `correct_with_erasures` are the plain syndromes, and Berlekamp-Massey
+/// locates every error count up to the bound.
+pub fn correct(codewords: &mut [u8], ec_len: usize) -> Result<usize, QRError> {
+    correct_with_erasures(codewords, ec_len, &[])
+}
+
+/// As `correct`, but additionally takes a list of codeword positions already
+/// known to be unreliable (e.g. modules the extractor sampled at a value
+/// close to the black/white threshold). Treating those as erasures rather
+/// than unknown errors lets the code correct up to `2*errors + erasures <=
+/// ec_len` instead of `2*errors <= ec_len`, since an erasure's location is
+/// already known and only its value needs recovering.
+pub fn correct_with_erasures(
+    codewords: &mut [u8],
+    ec_len: usize,
+    erasures: &[usize],
+) -> Result<usize, QRError> {
+    let gf = GaloisField::new();
+    let n = codewords.len();
+
+    if erasures.len() > ec_len {
+        return Err(QRError::new("More erasures than EC codewords"));
+    }
+
+    // S_i = codeword(alpha^i), i = 0..ec_len - ascending in i, so this is
+    // already in the ascending-degree order the rest of the module uses.
+    let syndromes: Vec<u8> = (0..ec_len)
+        .map(|i| eval(&gf, codewords, gf.pow(2, i)))
+        .collect();
+
+    if syndromes.iter().all(|&s| s == 0) {
+        return Ok(0);
+    }
+
+    let erasure_count = erasures.len();
+    let reversed_erasures: Vec<usize> = erasures.iter().map(|&pos| n - 1 - pos).collect();
+    let erasure_loc = erasure_locator(&gf, &reversed_erasures);
+
+    // Forney syndromes: the coefficients of `erasure_loc(x) * syndromes(x)`
+    // from degree `erasure_count` up to `ec_len - 1`, shifted down by
+    // `erasure_count`. This is the syndrome sequence with the known
+    // erasures' contribution already cancelled out, leaving `ec_len -
+    // erasure_count` degrees of freedom for Berlekamp-Massey to locate the
+    // remaining, unknown errors in.
+    let erasure_adjusted = poly_mul(&gf, &erasure_loc, &syndromes);
+    let forney_len = ec_len - erasure_count;
+    let forney_syndromes: Vec<u8> = (0..forney_len)
+        .map(|i| {
+            erasure_adjusted
+                .get(erasure_count + i)
+                .cloned()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let error_locator = berlekamp_massey(&gf, &forney_syndromes);
+    let new_errors = error_locator.len() - 1;
+    if 2 * new_errors + erasure_count > ec_len {
+        return Err(QRError::new("Too many errors/erasures to correct"));
+    }
+
+    // The full errata locator: erasures and newly-found errors share the
+    // same Chien search and Forney magnitude recovery below.
+    let sigma = poly_mul(&gf, &erasure_loc, &error_locator);
+    let total_errata = sigma.len() - 1;
+
+    // Chien search: the locator's roots give every errata position (erasures
+    // and newly found errors alike).
+    let mut errata_positions = vec![];
+    for i in 0..n {
+        if poly_eval(&gf, &sigma, gf.pow(2, (255 - i % 255) % 255)) == 0 {
+            errata_positions.push(n - 1 - i);
+        }
+    }
+
+    if errata_positions.len() != total_errata {
+        return Err(QRError::new(
+            "Errata locator roots did not match errata count",
+        ));
+    }
+
+    // Forney's algorithm: the magnitude of the error/erasure at each located position.
+    let omega = {
+        let mut product = poly_mul(&gf, &sigma, &syndromes);
+        product.truncate(ec_len);
+        product
+    };
+
+    // sigma' (formal derivative, ascending order): in GF(2^m), d/dx(sum a_i
+    // x^i) keeps only the odd-degree terms, each shifted down one degree.
+    let mut sigma_deriv = vec![0u8; sigma.len().saturating_sub(1)];
+    for (i, &c) in sigma.iter().enumerate() {
+        if i % 2 == 1 {
+            sigma_deriv[i - 1] = c;
+        }
+    }
+
+    for &pos in &errata_positions {
+        let location = gf.pow(2, n - 1 - pos);
+        let location_inv = gf.inv(location);
+        let numerator = poly_eval(&gf, &omega, location_inv);
+        let denominator = poly_eval(&gf, &sigma_deriv, location_inv);
+        if denominator == 0 {
+            return Err(QRError::new("Forney denominator was zero"));
+        }
+        let magnitude = gf.mul(gf.mul(numerator, gf.inv(denominator)), location);
+        codewords[pos] ^= magnitude;
+    }
+
+    Ok(new_errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Systematic Reed-Solomon encode: append `ec_len` parity bytes to `data`
+    /// so the result is a valid codeword, via standard polynomial long
+    /// division by `product(x - alpha^i)` for `i` in `0..ec_len`.
+    fn encode(data: &[u8], ec_len: usize) -> Vec<u8> {
+        let gf = GaloisField::new();
+
+        let mut generator = vec![1u8];
+        for i in 0..ec_len {
+            generator = poly_mul(&gf, &generator, &[gf.pow(2, i), 1]);
+        }
+        let generator: Vec<u8> = generator.into_iter().rev().collect();
+
+        let mut codewords = data.to_vec();
+        codewords.extend(vec![0u8; ec_len]);
+        for i in 0..data.len() {
+            let coef = codewords[i];
+            if coef != 0 {
+                for (j, &g) in generator.iter().enumerate() {
+                    codewords[i + j] ^= gf.mul(g, coef);
+                }
+            }
+        }
+
+        let mut out = data.to_vec();
+        out.extend_from_slice(&codewords[data.len()..]);
+        out
+    }
+
+    fn is_valid(codewords: &[u8], ec_len: usize) -> bool {
+        let gf = GaloisField::new();
+        (0..ec_len).all(|i| eval(&gf, codewords, gf.pow(2, i)) == 0)
+    }
+
+    #[test]
+    fn corrects_a_single_error() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let ec_len = 10;
+        let mut codewords = encode(&data, ec_len);
+
+        codewords[7] ^= 0x5A;
+
+        let corrected = correct(&mut codewords, ec_len).unwrap();
+        assert_eq!(corrected, 1);
+        assert!(is_valid(&codewords, ec_len));
+        assert_eq!(&codewords[..data.len()], &data[..]);
+    }
+
+    #[test]
+    fn corrects_up_to_ec_len_over_two_errors() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let ec_len = 10;
+        let mut codewords = encode(&data, ec_len);
+
+        for &pos in &[0usize, 3, 9, 15, 22] {
+            codewords[pos] ^= 0x77;
+        }
+
+        let corrected = correct(&mut codewords, ec_len).unwrap();
+        assert_eq!(corrected, 5);
+        assert!(is_valid(&codewords, ec_len));
+        assert_eq!(&codewords[..data.len()], &data[..]);
+    }
+
+    #[test]
+    fn rejects_more_errors_than_it_can_correct() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let ec_len = 10;
+        let mut codewords = encode(&data, ec_len);
+
+        for &pos in &[0usize, 2, 4, 6, 8, 10] {
+            codewords[pos] ^= 0x77;
+        }
+
+        assert!(correct(&mut codewords, ec_len).is_err());
+    }
+
+    #[test]
+    fn corrects_erasures_up_to_ec_len() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let ec_len = 10;
+        let mut codewords = encode(&data, ec_len);
+
+        let erasures: Vec<usize> = (0..ec_len).collect();
+        for &pos in &erasures {
+            codewords[pos] = 0;
+        }
+
+        let corrected = correct_with_erasures(&mut codewords, ec_len, &erasures).unwrap();
+        assert_eq!(corrected, 0);
+        assert!(is_valid(&codewords, ec_len));
+        assert_eq!(&codewords[..data.len()], &data[..]);
+    }
+
+    #[test]
+    fn corrects_a_mix_of_errors_and_erasures() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let ec_len = 10;
+        let mut codewords = encode(&data, ec_len);
+
+        codewords[5] ^= 0x33;
+        let erasures = vec![20usize, 21usize];
+        codewords[20] ^= 0x11;
+        codewords[21] ^= 0x99;
+
+        let corrected = correct_with_erasures(&mut codewords, ec_len, &erasures).unwrap();
+        assert_eq!(corrected, 1);
+        assert!(is_valid(&codewords, ec_len));
+        assert_eq!(&codewords[..data.len()], &data[..]);
+    }
+}
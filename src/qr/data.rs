@@ -0,0 +1,43 @@
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The encoding mode a segment of a symbol's data was written in
+/// (ISO/IEC 18004 Table 2's mode indicators).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+    Kanji,
+}
+
+/// One mode-indicator-delimited segment of a symbol's decoded data.
+///
+/// `text` is a best-effort `String` rendering (lossy for byte segments that
+/// aren't valid UTF-8), while `bytes` holds a byte-mode segment's raw,
+/// unmodified bytes so callers that need exact binary data aren't forced
+/// through a lossy `String` conversion; for numeric and alphanumeric
+/// segments `bytes` is the ASCII of `text`. For a byte-mode segment, `eci`
+/// carries the ECI designator value (if any) in effect when it was decoded,
+/// so callers can tell a Latin-1 byte segment from a Shift-JIS one; it is
+/// `None` for numeric and alphanumeric segments.
+#[derive(Debug, Clone)]
+pub struct DecodedSegment {
+    pub mode: Mode,
+    pub text: String,
+    pub bytes: Vec<u8>,
+    pub eci: Option<u32>,
+}
+
+impl DecodedSegment {
+    pub fn new(mode: Mode, text: String, bytes: Vec<u8>, eci: Option<u32>) -> DecodedSegment {
+        DecodedSegment {
+            mode,
+            text,
+            bytes,
+            eci,
+        }
+    }
+}
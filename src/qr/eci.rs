@@ -0,0 +1,55 @@
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// A subset of the ECI (Extended Channel Interpretation) designator values
+/// relevant to decoding byte-mode text, ISO/IEC 18004 Annex E / AIM ECI spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Latin1,
+    Utf8,
+}
+
+impl Charset {
+    /// Map an ECI assignment number to the charset it designates, defaulting
+    /// to UTF-8 for anything not explicitly handled.
+    pub fn from_eci(eci: u32) -> Charset {
+        match eci {
+            27 => Charset::Ascii,
+            1 | 3 => Charset::Latin1,
+            26 => Charset::Utf8,
+            _ => Charset::Utf8,
+        }
+    }
+
+    /// Decode `bytes` as this charset into a `String`. Invalid UTF-8
+    /// sequences are substituted; for ASCII, bytes above 0x7F are kept as
+    /// their Latin-1 characters.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            Charset::Ascii | Charset::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            Charset::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
+
+/// Read an ECI assignment number off the bitstream immediately following the
+/// ECI mode indicator `0111`.
+///
+/// The leading bits of the first byte give its length: `0xxxxxxx` is a single
+/// byte holding values 0-127, `10xxxxxx` plus one more byte covers 128-16383,
+/// and `110xxxxx` plus two more bytes covers 16384-999999.
+pub fn read_eci_designator<F: FnMut(u32) -> u32>(mut read_bits: F) -> u32 {
+    let first = read_bits(8);
+
+    if first & 0x80 == 0 {
+        first
+    } else if first & 0xC0 == 0x80 {
+        let second = read_bits(8);
+        ((first & 0x3F) << 8) | second
+    } else {
+        let second = read_bits(8);
+        let third = read_bits(8);
+        ((first & 0x1F) << 16) | (second << 8) | third
+    }
+}
@@ -0,0 +1,211 @@
+/// Error correction level of a QR symbol, as encoded in the format information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ECLevel {
+    LOW,
+    MEDIUM,
+    QUARTILE,
+    HIGH,
+}
+
+/// The decoded contents of a symbol's 15-bit format information field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatInfo {
+    pub level: ECLevel,
+    pub mask: u8,
+}
+
+/// XOR mask the format bits are always combined with, so that the all-zero
+/// format info (level M, mask 0) never maps to an all-white reserved module.
+const FORMAT_MASK: u16 = 0x5412;
+
+/// Generator polynomial for the BCH(15,5) code protecting the format bits,
+/// ISO/IEC 18004 Annex C: x^10 + x^8 + x^5 + x^4 + x^2 + x + 1.
+const FORMAT_GENERATOR: u32 = 0x537;
+
+/// Encode 5 data bits (2 bits EC level + 3 bits mask pattern) into their
+/// 15-bit BCH(15,5) codeword, via standard polynomial long division.
+fn bch_encode(data: u8) -> u16 {
+    let mut remainder = (data as u32) << 10;
+    for i in (10..15).rev() {
+        if remainder & (1 << i) != 0 {
+            remainder ^= FORMAT_GENERATOR << (i - 10);
+        }
+    }
+    (((data as u32) << 10) | remainder) as u16
+}
+
+/// Recover the format information from the 15 bits read off a symbol.
+///
+/// The stored codeword is always XORed with `FORMAT_MASK` before being placed
+/// in the symbol, so that's undone first. The result is then compared against
+/// all 32 valid BCH(15,5) codewords and corrected to whichever is nearest in
+/// Hamming distance; the code can correct up to 3 bit errors, matching its
+/// minimum distance of 7. Returns `None` if no codeword is within range.
+pub fn decode_format_bits(bits: u16) -> Option<FormatInfo> {
+    let unmasked = bits ^ FORMAT_MASK;
+
+    let mut best_data = None;
+    let mut best_distance = 4;
+
+    for data in 0..32u8 {
+        let distance = (bch_encode(data) ^ unmasked).count_ones();
+        if distance < best_distance {
+            best_distance = distance;
+            best_data = Some(data);
+        }
+    }
+
+    best_data.map(|data| {
+        let level = match (data >> 3) & 0x3 {
+            0b01 => ECLevel::LOW,
+            0b00 => ECLevel::MEDIUM,
+            0b11 => ECLevel::QUARTILE,
+            0b10 => ECLevel::HIGH,
+            _ => unreachable!(),
+        };
+
+        FormatInfo {
+            level,
+            mask: data & 0x7,
+        }
+    })
+}
+
+/// Evaluate the data mask condition for `mask` (0-7) at module `(row, col)`,
+/// as specified in ISO/IEC 18004 Table 10. A module is inverted wherever this
+/// returns `true`.
+pub fn apply_mask(mask: u8, row: u32, col: u32) -> bool {
+    let (row, col) = (row as i64, col as i64);
+    match mask {
+        0 => (row + col) % 2 == 0,
+        1 => row % 2 == 0,
+        2 => col % 3 == 0,
+        3 => (row + col) % 3 == 0,
+        4 => (row / 2 + col / 3) % 2 == 0,
+        5 => (row * col) % 2 + (row * col) % 3 == 0,
+        6 => ((row * col) % 2 + (row * col) % 3) % 2 == 0,
+        7 => ((row + col) % 2 + (row * col) % 3) % 2 == 0,
+        _ => false,
+    }
+}
+
+/// XOR mask a Micro QR symbol's format bits are combined with. Micro symbols
+/// use the same BCH(15,5) code as standard symbols but a different mask
+/// constant and bit layout, so they need their own decode path.
+const MICRO_FORMAT_MASK: u16 = 0x4445;
+
+/// Maps a Micro QR format info's 3-bit "symbol number" to the version (1-4,
+/// i.e. M1-M4) and EC level it designates, per ISO/IEC 18004 Table 12. M1 has
+/// no error correction at all, hence the `None`.
+const MICRO_SYMBOL_NUMBERS: [(u32, Option<ECLevel>); 8] = [
+    (1, None),
+    (2, Some(ECLevel::LOW)),
+    (2, Some(ECLevel::MEDIUM)),
+    (3, Some(ECLevel::LOW)),
+    (3, Some(ECLevel::MEDIUM)),
+    (4, Some(ECLevel::LOW)),
+    (4, Some(ECLevel::MEDIUM)),
+    (4, Some(ECLevel::QUARTILE)),
+];
+
+/// The decoded contents of a Micro QR symbol's 15-bit format information
+/// field. Unlike a standard symbol, the 5 data bits split into a 3-bit
+/// "symbol number" (which folds together the version and EC level) and a
+/// 2-bit mask pattern, rather than a 2-bit level and 3-bit mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicroFormatInfo {
+    pub version: u32,
+    pub level: Option<ECLevel>,
+    pub mask: u8,
+}
+
+/// As `decode_format_bits`, but for the 15 format bits read off a Micro QR
+/// symbol's single finder corner.
+pub fn decode_micro_format_bits(bits: u16) -> Option<MicroFormatInfo> {
+    let unmasked = bits ^ MICRO_FORMAT_MASK;
+
+    let mut best_data = None;
+    let mut best_distance = 4;
+
+    for data in 0..32u8 {
+        let distance = (bch_encode(data) ^ unmasked).count_ones();
+        if distance < best_distance {
+            best_distance = distance;
+            best_data = Some(data);
+        }
+    }
+
+    best_data.map(|data| {
+        let (version, level) = MICRO_SYMBOL_NUMBERS[((data >> 2) & 0x7) as usize];
+        MicroFormatInfo {
+            version,
+            level,
+            mask: data & 0x3,
+        }
+    })
+}
+
+/// Evaluate the data mask condition for a Micro QR `mask` (0-3) at module
+/// `(row, col)`, per ISO/IEC 18004 Table 13. These are the standard symbol's
+/// mask formulas 1, 4, 6 and 7 (see `apply_mask`), renumbered 0-3.
+pub fn apply_micro_mask(mask: u8, row: u32, col: u32) -> bool {
+    let (row, col) = (row as i64, col as i64);
+    match mask {
+        0 => row % 2 == 0,
+        1 => (row / 2 + col / 3) % 2 == 0,
+        2 => ((row * col) % 2 + (row * col) % 3) % 2 == 0,
+        3 => ((row + col) % 2 + (row * col) % 3) % 2 == 0,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_every_level_and_mask_with_up_to_three_bit_errors() {
+        for data in 0..32u8 {
+            let codeword = bch_encode(data) ^ FORMAT_MASK;
+
+            for errors in 0..4u32 {
+                let mut bits = codeword;
+                for bit in 0..errors {
+                    bits ^= 1 << bit;
+                }
+
+                let info = decode_format_bits(bits).unwrap();
+                let expected_level = match (data >> 3) & 0x3 {
+                    0b01 => ECLevel::LOW,
+                    0b00 => ECLevel::MEDIUM,
+                    0b11 => ECLevel::QUARTILE,
+                    0b10 => ECLevel::HIGH,
+                    _ => unreachable!(),
+                };
+                assert_eq!(info.level, expected_level);
+                assert_eq!(info.mask, data & 0x7);
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_every_micro_symbol_number_and_mask_with_up_to_three_bit_errors() {
+        for data in 0..32u8 {
+            let codeword = bch_encode(data) ^ MICRO_FORMAT_MASK;
+
+            for errors in 0..4u32 {
+                let mut bits = codeword;
+                for bit in 0..errors {
+                    bits ^= 1 << bit;
+                }
+
+                let info = decode_micro_format_bits(bits).unwrap();
+                let (expected_version, expected_level) =
+                    MICRO_SYMBOL_NUMBERS[((data >> 2) & 0x7) as usize];
+                assert_eq!(info.version, expected_version);
+                assert_eq!(info.level, expected_level);
+                assert_eq!(info.mask, data & 0x3);
+            }
+        }
+    }
+}
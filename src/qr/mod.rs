@@ -1,7 +1,21 @@
+#[cfg(feature = "std")]
 use std::error::Error;
+#[cfg(feature = "std")]
 use std::fmt;
+#[cfg(feature = "std")]
 use std::ops::Index;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use core::ops::Index;
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use point::Point;
 
 use self::format::ECLevel;
@@ -9,6 +23,7 @@ use self::format::ECLevel;
 pub mod blocks;
 pub mod correct;
 pub mod data;
+pub mod eci;
 pub mod format;
 
 #[derive(Debug)]
@@ -16,16 +31,50 @@ pub struct QRData {
     pub data: Vec<u8>,
     pub version: u32,
     pub side: u32,
+    pub level: ECLevel,
+    pub mask: u8,
+    /// Per-module reliability, parallel to `data`: `true` where the extractor
+    /// sampled a grey value too close to the black/white threshold to trust.
+    /// Empty when the extractor didn't track confidence.
+    pub erasures: Vec<bool>,
+    /// Whether `version` addresses a Micro QR symbol (M1-M4) rather than a
+    /// standard one; Micro symbols use a smaller side formula and their own
+    /// format info, block layout and bitstream widths throughout `qr`.
+    pub micro: bool,
 }
 
 impl QRData {
-    pub fn new(data: Vec<u8>, version: u32) -> QRData {
+    pub fn new(
+        data: Vec<u8>,
+        version: u32,
+        level: ECLevel,
+        mask: u8,
+        erasures: Vec<bool>,
+        micro: bool,
+    ) -> QRData {
+        let side = if micro {
+            2 * version + 9
+        } else {
+            4 * version + 17
+        };
         QRData {
             data,
             version,
-            side: 4 * version + 17,
+            side,
+            level,
+            mask,
+            erasures,
+            micro,
         }
     }
+
+    /// Whether the module at `(x, y)` was flagged as an erasure.
+    pub fn is_erasure(&self, x: u32, y: u32) -> bool {
+        self.erasures
+            .get((y * self.side + x) as usize)
+            .cloned()
+            .unwrap_or(false)
+    }
 }
 
 impl Index<[u32; 2]> for QRData {
@@ -41,13 +90,64 @@ impl Index<[u32; 2]> for QRData {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct QRLocation {
     pub top_left: Point,
     pub top_right: Point,
     pub bottom_left: Point,
     pub module_size: f64,
     pub version: u32,
+    /// Whether this location is a Micro QR symbol (M1-M4), which has a
+    /// single finder pattern rather than the usual three. `top_right` and
+    /// `bottom_left` are meaningless for a Micro location and simply mirror
+    /// `top_left`, since extraction only ever samples off `top_left` and
+    /// `module_size`.
+    pub micro: bool,
+}
+
+/// The full metadata of a successfully decoded symbol: the payload text
+/// alongside everything `QRLocation`/`QRData` captured about the symbol
+/// itself, for callers that need more than just the text - quality gating,
+/// overlay drawing, or disambiguating multiple symbols in one image.
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    pub text: String,
+    pub version: u32,
+    pub level: ECLevel,
+    pub mask: u8,
+    pub top_left: Point,
+    pub top_right: Point,
+    pub bottom_left: Point,
+    pub module_size: f64,
+    /// Number of codeword errors corrected by Reed-Solomon while decoding
+    /// this symbol, summed across all of its blocks.
+    pub errors_corrected: u32,
+}
+
+impl ScanResult {
+    pub fn new(
+        text: String,
+        version: u32,
+        level: ECLevel,
+        mask: u8,
+        top_left: Point,
+        top_right: Point,
+        bottom_left: Point,
+        module_size: f64,
+        errors_corrected: u32,
+    ) -> ScanResult {
+        ScanResult {
+            text,
+            version,
+            level,
+            mask,
+            top_left,
+            top_right,
+            bottom_left,
+            module_size,
+            errors_corrected,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -75,11 +175,334 @@ impl BlockInfo {
     }
 }
 
+/// One row of the ISO/IEC 18004 error correction block table for a single version.
+///
+/// Each entry is `(ec_cap, group_1_blocks, group_1_data, group_2_blocks, group_2_data)`.
+/// `ec_cap` is the number of EC codewords per block, which is constant across both
+/// groups for a given version/level. A `group_2_blocks` of `0` means the version/level
+/// only has a single group of blocks.
+type VersionRow = [(u8, u8, u16, u8, u16); 4];
+
+/// `block_info` table indexed by `version - 1`, with each row giving the L, M, Q, H
+/// entries in that order, as specified in ISO/IEC 18004 Table 9.
+const BLOCK_TABLE: [VersionRow; 40] = [
+    [
+        (7, 1, 19, 0, 0),
+        (10, 1, 16, 0, 0),
+        (13, 1, 13, 0, 0),
+        (17, 1, 9, 0, 0),
+    ],
+    [
+        (10, 1, 34, 0, 0),
+        (16, 1, 28, 0, 0),
+        (22, 1, 22, 0, 0),
+        (28, 1, 16, 0, 0),
+    ],
+    [
+        (15, 1, 55, 0, 0),
+        (26, 1, 44, 0, 0),
+        (18, 2, 17, 0, 0),
+        (22, 2, 13, 0, 0),
+    ],
+    [
+        (20, 1, 80, 0, 0),
+        (18, 2, 32, 0, 0),
+        (26, 2, 24, 0, 0),
+        (16, 4, 9, 0, 0),
+    ],
+    [
+        (26, 1, 108, 0, 0),
+        (24, 2, 43, 0, 0),
+        (18, 2, 15, 2, 16),
+        (22, 2, 11, 2, 12),
+    ],
+    [
+        (18, 2, 68, 0, 0),
+        (16, 4, 27, 0, 0),
+        (24, 4, 19, 0, 0),
+        (28, 4, 15, 0, 0),
+    ],
+    [
+        (20, 2, 78, 0, 0),
+        (18, 4, 31, 0, 0),
+        (18, 2, 14, 4, 15),
+        (26, 4, 13, 1, 14),
+    ],
+    [
+        (24, 2, 97, 0, 0),
+        (22, 2, 38, 2, 39),
+        (22, 4, 18, 2, 19),
+        (26, 4, 14, 2, 15),
+    ],
+    [
+        (30, 2, 116, 0, 0),
+        (22, 3, 36, 2, 37),
+        (20, 4, 16, 4, 17),
+        (24, 4, 12, 4, 13),
+    ],
+    [
+        (18, 2, 68, 2, 69),
+        (26, 4, 43, 1, 44),
+        (24, 6, 19, 2, 20),
+        (28, 6, 15, 2, 16),
+    ],
+    [
+        (20, 4, 81, 0, 0),
+        (30, 1, 50, 4, 51),
+        (28, 4, 22, 4, 23),
+        (24, 3, 12, 8, 13),
+    ],
+    [
+        (24, 2, 92, 2, 93),
+        (22, 6, 36, 2, 37),
+        (26, 4, 20, 6, 21),
+        (28, 7, 14, 4, 15),
+    ],
+    [
+        (26, 4, 107, 0, 0),
+        (22, 8, 37, 1, 38),
+        (24, 8, 20, 4, 21),
+        (22, 12, 11, 4, 12),
+    ],
+    [
+        (30, 3, 115, 1, 116),
+        (24, 4, 40, 5, 41),
+        (20, 11, 16, 5, 17),
+        (24, 11, 12, 5, 13),
+    ],
+    [
+        (22, 5, 87, 1, 88),
+        (24, 5, 41, 5, 42),
+        (30, 5, 24, 7, 25),
+        (24, 11, 12, 7, 13),
+    ],
+    [
+        (24, 5, 98, 1, 99),
+        (28, 7, 45, 3, 46),
+        (24, 15, 19, 2, 20),
+        (30, 3, 15, 13, 16),
+    ],
+    [
+        (28, 1, 107, 5, 108),
+        (28, 10, 46, 1, 47),
+        (28, 1, 22, 15, 23),
+        (28, 2, 14, 17, 15),
+    ],
+    [
+        (30, 5, 120, 1, 121),
+        (26, 9, 43, 4, 44),
+        (28, 17, 22, 1, 23),
+        (28, 2, 14, 19, 15),
+    ],
+    [
+        (28, 3, 113, 4, 114),
+        (26, 3, 44, 11, 45),
+        (26, 17, 21, 4, 22),
+        (26, 9, 13, 16, 14),
+    ],
+    [
+        (28, 3, 107, 5, 108),
+        (26, 3, 41, 13, 42),
+        (30, 15, 24, 5, 25),
+        (28, 15, 15, 10, 16),
+    ],
+    [
+        (28, 4, 116, 4, 117),
+        (26, 17, 42, 0, 0),
+        (28, 17, 22, 6, 23),
+        (30, 19, 16, 6, 17),
+    ],
+    [
+        (28, 2, 111, 7, 112),
+        (28, 17, 46, 0, 0),
+        (30, 7, 24, 16, 25),
+        (24, 34, 13, 0, 0),
+    ],
+    [
+        (30, 4, 121, 5, 122),
+        (28, 4, 47, 14, 48),
+        (30, 11, 24, 14, 25),
+        (30, 16, 15, 14, 16),
+    ],
+    [
+        (30, 6, 117, 4, 118),
+        (28, 6, 45, 14, 46),
+        (30, 11, 24, 16, 25),
+        (30, 30, 16, 2, 17),
+    ],
+    [
+        (26, 8, 106, 4, 107),
+        (28, 8, 47, 13, 48),
+        (30, 7, 24, 22, 25),
+        (30, 22, 15, 13, 16),
+    ],
+    [
+        (28, 10, 114, 2, 115),
+        (28, 19, 46, 4, 47),
+        (28, 28, 22, 6, 23),
+        (30, 33, 16, 4, 17),
+    ],
+    [
+        (30, 8, 122, 4, 123),
+        (28, 22, 45, 3, 46),
+        (30, 8, 23, 26, 24),
+        (30, 12, 15, 28, 16),
+    ],
+    [
+        (30, 3, 117, 10, 118),
+        (28, 3, 45, 23, 46),
+        (30, 4, 24, 31, 25),
+        (30, 11, 15, 31, 16),
+    ],
+    [
+        (30, 7, 116, 7, 117),
+        (28, 21, 45, 7, 46),
+        (30, 1, 23, 37, 24),
+        (30, 19, 15, 26, 16),
+    ],
+    [
+        (30, 5, 115, 10, 116),
+        (28, 19, 47, 10, 48),
+        (30, 15, 24, 25, 25),
+        (30, 23, 15, 25, 16),
+    ],
+    [
+        (30, 13, 115, 3, 116),
+        (28, 2, 46, 29, 47),
+        (30, 42, 24, 1, 25),
+        (30, 23, 15, 28, 16),
+    ],
+    [
+        (30, 17, 115, 0, 0),
+        (28, 10, 46, 23, 47),
+        (30, 10, 24, 35, 25),
+        (30, 19, 15, 35, 16),
+    ],
+    [
+        (30, 17, 115, 1, 116),
+        (28, 14, 46, 21, 47),
+        (30, 29, 24, 19, 25),
+        (30, 11, 15, 46, 16),
+    ],
+    [
+        (30, 13, 115, 6, 116),
+        (28, 14, 46, 23, 47),
+        (30, 44, 24, 7, 25),
+        (30, 59, 16, 1, 17),
+    ],
+    [
+        (30, 12, 121, 7, 122),
+        (28, 12, 47, 26, 48),
+        (30, 39, 24, 14, 25),
+        (30, 22, 15, 41, 16),
+    ],
+    [
+        (30, 6, 121, 14, 122),
+        (28, 6, 47, 34, 48),
+        (30, 46, 24, 10, 25),
+        (30, 2, 15, 64, 16),
+    ],
+    [
+        (30, 17, 122, 4, 123),
+        (28, 29, 46, 14, 47),
+        (30, 49, 24, 10, 25),
+        (30, 24, 15, 46, 16),
+    ],
+    [
+        (30, 4, 122, 18, 123),
+        (28, 13, 46, 32, 47),
+        (30, 48, 24, 14, 25),
+        (30, 42, 15, 32, 16),
+    ],
+    [
+        (30, 20, 117, 4, 118),
+        (28, 40, 47, 7, 48),
+        (30, 43, 24, 22, 25),
+        (30, 10, 15, 67, 16),
+    ],
+    [
+        (30, 19, 118, 6, 119),
+        (28, 18, 47, 31, 48),
+        (30, 34, 24, 34, 25),
+        (30, 20, 15, 61, 16),
+    ],
+];
+
+/// Look up the block layout for a QR symbol of the given version and EC level.
+///
+/// Returns one `BlockInfo` per block, group 1 first followed by group 2 (if the
+/// version/level splits its codewords across two groups with differing data
+/// lengths). `qr::blocks` de-interleaves codewords by reading block by block in
+/// this order.
 pub fn block_info(version: u32, level: ECLevel) -> Option<Vec<BlockInfo>> {
-    match (version, level) {
-        (1, ECLevel::MEDIUM) => Some(vec![BlockInfo::new(1, 26, 16, 4)]),
-        _ => None,
+    if version < 1 || version > 40 {
+        return None;
+    }
+
+    let row = &BLOCK_TABLE[(version - 1) as usize];
+    let (ec_cap, g1_count, g1_data, g2_count, g2_data) = match level {
+        ECLevel::LOW => row[0],
+        ECLevel::MEDIUM => row[1],
+        ECLevel::QUARTILE => row[2],
+        ECLevel::HIGH => row[3],
+    };
+
+    let mut blocks = Vec::with_capacity((g1_count + g2_count) as usize);
+    for _ in 0..g1_count {
+        blocks.push(BlockInfo::new(
+            g1_count,
+            g1_data as u8 + ec_cap,
+            g1_data as u8,
+            ec_cap,
+        ));
+    }
+    for _ in 0..g2_count {
+        blocks.push(BlockInfo::new(
+            g2_count,
+            g2_data as u8 + ec_cap,
+            g2_data as u8,
+            ec_cap,
+        ));
+    }
+
+    Some(blocks)
+}
+
+/// `micro_block_info` table indexed by `version - 1` (M1-M4), giving the
+/// `(ec_cap, data_codewords)` entry for each EC level in the order L, M, Q, as
+/// specified in ISO/IEC 18004 Table 7. Micro symbols are always a single
+/// block, so there's no group split to track. M1 has no EC level at all, so
+/// its row only ever uses the L slot; M2 and M3 don't support Q.
+const MICRO_BLOCK_TABLE: [[(u8, u8); 3]; 4] = [
+    [(2, 3), (0, 0), (0, 0)],
+    [(5, 5), (6, 4), (0, 0)],
+    [(6, 11), (8, 9), (0, 0)],
+    [(8, 16), (10, 14), (14, 10)],
+];
+
+/// As `block_info`, but for a Micro QR symbol (`version` 1-4, i.e. M1-M4).
+/// `level` is `None` only for M1, which has no error correction codewords
+/// whatsoever. Every Micro symbol is a single block, so this always returns
+/// exactly one `BlockInfo`.
+pub fn micro_block_info(version: u32, level: Option<ECLevel>) -> Option<Vec<BlockInfo>> {
+    if version < 1 || version > 4 {
+        return None;
+    }
+
+    let row = &MICRO_BLOCK_TABLE[(version - 1) as usize];
+    let (ec_cap, data) = match (version, level) {
+        (1, None) => row[0],
+        (_, Some(ECLevel::LOW)) => row[0],
+        (_, Some(ECLevel::MEDIUM)) => row[1],
+        (_, Some(ECLevel::QUARTILE)) => row[2],
+        _ => return None,
+    };
+
+    if data == 0 {
+        return None;
     }
+
+    Some(vec![BlockInfo::new(1, data + ec_cap, data, ec_cap)])
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +510,13 @@ pub struct QRError {
     msg: String,
 }
 
+impl QRError {
+    pub fn new<S: Into<String>>(msg: S) -> QRError {
+        QRError { msg: msg.into() }
+    }
+}
+
+#[cfg(feature = "std")]
 impl Error for QRError {
     fn description(&self) -> &str {
         &self.msg
@@ -97,4 +527,4 @@ impl fmt::Display for QRError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "QRError: {}", self.msg)
     }
-}
\ No newline at end of file
+}